@@ -7,7 +7,7 @@ use embassy_time::{with_timeout, Duration, TimeoutError, Timer};
 use esp_hal::clock::CpuClock;
 use esp_hal::gpio::{Level, Output};
 use esp_hal::uart::{Config, DataBits, Parity, StopBits, Uart};
-use sbus_protocol::SbusParser;
+use sbus_protocol::io::AsyncSbusReader;
 use {defmt_rtt as _, esp_backtrace as _};
 
 #[esp_hal_embassy::main]
@@ -32,29 +32,17 @@ async fn main(_spawner: Spawner) {
         .with_rx(rx_pin.inverted())
         .with_tx(tx_pin.inverted());
 
-    let mut uart = uart0.into_async();
-
-    let mut buf: [u8; 25] = [0; 25];
-    let mut sbus = SbusParser::new();
+    let uart = uart0.into_async();
+    let mut reader = AsyncSbusReader::new(uart);
 
     info!("Starting reading loop!");
     let mut led = Output::new(peripherals.GPIO8, Level::Low);
     led.set_high();
     loop {
-        let result = with_timeout(Duration::from_millis(500), uart.read_async(&mut buf)).await;
-        match result {
-            Ok(Ok(size)) => {
-                for result in sbus.iter_packets(&buf[..size]) {
-                    match result {
-                        Ok(packet) => info!("{:?}", packet.channels),
-                        Err(e) => info!("{:?}", e),
-                    }
-                }
-            }
-            Ok(Err(read_error)) => {
-                info!("reading error {:?}", read_error)
-            }
-            Err(TimeoutError) => sbus.reset(),
+        match with_timeout(Duration::from_millis(500), reader.next_packet()).await {
+            Ok(Ok(packet)) => info!("{:?}", packet.channels),
+            Ok(Err(e)) => info!("{:?}", e),
+            Err(TimeoutError) => reader.reset(),
         }
     }
 }
@@ -0,0 +1,242 @@
+//! FrSky FPort decoding/encoding.
+//!
+//! FPort frames the same channel payload as SBUS, but delimits, byte-stuffs,
+//! and checksums it over a single inverted UART: `0x7E`-delimited, with
+//! `0x7E`/`0x7D` escaped as `0x7D 0x5E`/`0x7D 0x5D`.
+
+use crate::{encode_packet, RawSbusPacket, SbusPacket, SBUS_FOOTER, SBUS_HEADER, SBUS_PACKET_SIZE};
+
+const FPORT_DELIMITER: u8 = 0x7E;
+const FPORT_ESCAPE: u8 = 0x7D;
+const FPORT_ESCAPE_XOR: u8 = 0x20;
+
+const FPORT_FRAME_LEN: u8 = 0x19;
+const FPORT_CONTROL_TYPE: u8 = 0x00;
+const FPORT_PAYLOAD_SIZE: usize = 24;
+/// Length of a de-stuffed control frame: `len + type + payload + checksum`.
+const FPORT_FRAME_SIZE: usize = 2 + FPORT_PAYLOAD_SIZE + 1;
+/// Worst-case stuffed frame: every body byte escaped, plus the two delimiters.
+pub const FPORT_MAX_FRAME_SIZE: usize = 2 + 2 * FPORT_FRAME_SIZE;
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FportError {
+    InvalidLength(u8),
+    InvalidType(u8),
+    InvalidChecksum(u8),
+}
+
+#[derive(Debug, Default, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
+enum FportState {
+    #[default]
+    AwaitingStart,
+    Reading(usize),
+}
+
+#[derive(Debug, Default)]
+pub struct FportParser {
+    buffer: [u8; FPORT_FRAME_SIZE],
+    state: FportState,
+    escaped: bool,
+}
+
+pub struct FportPacketIterator<'a, 'b> {
+    parser: &'a mut FportParser,
+    remaining_data: &'b [u8],
+}
+
+impl Iterator for FportPacketIterator<'_, '_> {
+    type Item = Result<SbusPacket, FportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining_data.is_empty() {
+                break;
+            }
+
+            let byte = self.remaining_data[0];
+            self.remaining_data = &self.remaining_data[1..];
+
+            if let Some(result) = self.parser.push_byte(byte) {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+impl FportParser {
+    pub fn new() -> Self {
+        Self {
+            buffer: [0; FPORT_FRAME_SIZE],
+            state: FportState::AwaitingStart,
+            escaped: false,
+        }
+    }
+
+    pub fn push_byte(&mut self, byte: u8) -> Option<Result<SbusPacket, FportError>> {
+        match self.state {
+            FportState::AwaitingStart => {
+                if byte == FPORT_DELIMITER {
+                    self.state = FportState::Reading(0);
+                }
+                None
+            }
+            FportState::Reading(n) => {
+                if byte == FPORT_DELIMITER && !self.escaped {
+                    self.state = FportState::Reading(0);
+                    return Some(self.try_parse(n));
+                }
+                if byte == FPORT_ESCAPE && !self.escaped {
+                    self.escaped = true;
+                    return None;
+                }
+                let value = if self.escaped {
+                    byte ^ FPORT_ESCAPE_XOR
+                } else {
+                    byte
+                };
+                self.escaped = false;
+                if n < FPORT_FRAME_SIZE {
+                    self.buffer[n] = value;
+                }
+                self.state = FportState::Reading(n + 1);
+                None
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = FportState::AwaitingStart;
+        self.escaped = false;
+    }
+
+    fn try_parse(&self, len: usize) -> Result<SbusPacket, FportError> {
+        if len != FPORT_FRAME_SIZE {
+            return Err(FportError::InvalidLength(len as u8));
+        }
+        if self.buffer[0] != FPORT_FRAME_LEN {
+            return Err(FportError::InvalidLength(self.buffer[0]));
+        }
+        if self.buffer[1] != FPORT_CONTROL_TYPE {
+            return Err(FportError::InvalidType(self.buffer[1]));
+        }
+
+        let checksum = self.buffer[FPORT_FRAME_SIZE - 1];
+        let expected_checksum = fport_checksum(&self.buffer[..FPORT_FRAME_SIZE - 1]);
+        if checksum != expected_checksum {
+            return Err(FportError::InvalidChecksum(checksum));
+        }
+
+        let payload = &self.buffer[2..2 + FPORT_PAYLOAD_SIZE];
+        let mut raw = [0u8; SBUS_PACKET_SIZE];
+        raw[0] = SBUS_HEADER;
+        raw[1..24].copy_from_slice(&payload[..23]);
+        raw[24] = SBUS_FOOTER;
+        Ok(SbusPacket::parse(&RawSbusPacket::new(&raw)))
+    }
+
+    pub fn iter_packets<'a, 'b>(&'a mut self, data: &'b [u8]) -> FportPacketIterator<'a, 'b> {
+        FportPacketIterator {
+            parser: self,
+            remaining_data: data,
+        }
+    }
+}
+
+fn fport_checksum(bytes: &[u8]) -> u8 {
+    let sum: u32 = bytes.iter().map(|&b| b as u32).sum();
+    (0xFFu32.wrapping_sub(sum % 0x100)) as u8
+}
+
+/// Encodes `packet` as a stuffed FPort control frame into `buf`, returning
+/// the number of bytes written. `buf` must be at least [`FPORT_MAX_FRAME_SIZE`]
+/// bytes long to fit the worst case where every body byte is escaped.
+pub fn encode_fport(buf: &mut [u8; FPORT_MAX_FRAME_SIZE], packet: &SbusPacket) -> usize {
+    let mut raw = [0u8; SBUS_PACKET_SIZE];
+    encode_packet(&mut raw, packet);
+
+    let mut body = [0u8; FPORT_FRAME_SIZE];
+    body[0] = FPORT_FRAME_LEN;
+    body[1] = FPORT_CONTROL_TYPE;
+    body[2..25].copy_from_slice(&raw[1..24]);
+    body[FPORT_FRAME_SIZE - 1] = fport_checksum(&body[..FPORT_FRAME_SIZE - 1]);
+
+    let mut idx = 0;
+    buf[idx] = FPORT_DELIMITER;
+    idx += 1;
+    for &b in body.iter() {
+        if b == FPORT_DELIMITER || b == FPORT_ESCAPE {
+            buf[idx] = FPORT_ESCAPE;
+            idx += 1;
+            buf[idx] = b ^ FPORT_ESCAPE_XOR;
+            idx += 1;
+        } else {
+            buf[idx] = b;
+            idx += 1;
+        }
+    }
+    buf[idx] = FPORT_DELIMITER;
+    idx += 1;
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+
+    fn sample_packet() -> SbusPacket {
+        SbusPacket {
+            channels: [
+                992, 992, 352, 992, 352, 352, 352, 352, 352, 352, 992, 992, 0, 0, 0, 0,
+            ],
+            channel_17: true,
+            channel_18: true,
+            failsafe: false,
+            frame_lost: false,
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let packet = sample_packet();
+        let mut buf = [0u8; FPORT_MAX_FRAME_SIZE];
+        let len = encode_fport(&mut buf, &packet);
+
+        let mut parser = FportParser::new();
+        let results: std::vec::Vec<Result<SbusPacket, FportError>> =
+            parser.iter_packets(&buf[..len]).collect();
+        assert!(results.len() == 1);
+        assert!(results[0] == Ok(packet));
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_checksum() {
+        let packet = sample_packet();
+        let mut buf = [0u8; FPORT_MAX_FRAME_SIZE];
+        let len = encode_fport(&mut buf, &packet);
+        buf[len - 2] ^= 0xFF;
+
+        let mut parser = FportParser::new();
+        let results: std::vec::Vec<Result<SbusPacket, FportError>> =
+            parser.iter_packets(&buf[..len]).collect();
+        assert!(matches!(results[0], Err(FportError::InvalidChecksum(_))));
+    }
+
+    #[test]
+    fn test_stuffing_round_trips_delimiter_bytes() {
+        // Force a channel value that places an 0x7E byte inside the payload
+        // so the stuffing/de-stuffing path is actually exercised.
+        let mut packet = sample_packet();
+        packet.channels[0] = 0x07E & 0x7FF;
+        let mut buf = [0u8; FPORT_MAX_FRAME_SIZE];
+        let len = encode_fport(&mut buf, &packet);
+
+        let mut parser = FportParser::new();
+        let results: std::vec::Vec<Result<SbusPacket, FportError>> =
+            parser.iter_packets(&buf[..len]).collect();
+        assert!(results.len() == 1);
+        assert!(results[0] == Ok(packet));
+    }
+}
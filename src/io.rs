@@ -0,0 +1,399 @@
+//! Streaming decoders that drive [`SbusParser`] from a byte-oriented reader
+//! instead of a pre-filled `&[u8]` slice: [`SbusReader`] over
+//! [`embedded_io::Read`], [`AsyncSbusReader`] over [`embedded_io_async::Read`].
+
+use crate::SbusParserError;
+#[cfg(any(feature = "embedded-io", feature = "embedded-io-async"))]
+use crate::{SbusPacket, SbusParser};
+
+#[cfg(feature = "futures")]
+extern crate alloc;
+
+/// Error returned while streaming packets out of a reader: either the
+/// underlying transport failed, it reached EOF, or a complete frame was read
+/// but rejected by the parser.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SbusReadError<E> {
+    Io(E),
+    Eof,
+    Parser(SbusParserError),
+}
+
+/// Blocking streaming decoder generic over [`embedded_io::Read`].
+#[cfg(feature = "embedded-io")]
+pub struct SbusReader<R> {
+    reader: R,
+    parser: SbusParser,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<R: embedded_io::Read> SbusReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: SbusParser::new(),
+        }
+    }
+
+    /// Reads bytes one at a time until a full packet has been decoded.
+    pub fn read_packet(&mut self) -> Result<SbusPacket, SbusReadError<R::Error>> {
+        loop {
+            let mut byte = [0u8; 1];
+            let n = self.reader.read(&mut byte).map_err(SbusReadError::Io)?;
+            if n == 0 {
+                return Err(SbusReadError::Eof);
+            }
+            if let Some(result) = self.parser.push_byte(byte[0]) {
+                return result.map_err(SbusReadError::Parser);
+            }
+        }
+    }
+
+    /// Forces the underlying parser back to [`crate::State::AwaitingHead`],
+    /// e.g. after a read timeout.
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
+}
+
+/// Async streaming decoder generic over [`embedded_io_async::Read`].
+#[cfg(feature = "embedded-io-async")]
+pub struct AsyncSbusReader<R> {
+    reader: R,
+    parser: SbusParser,
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<R: embedded_io_async::Read> AsyncSbusReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: SbusParser::new(),
+        }
+    }
+
+    /// Reads bytes one at a time until a full packet has been decoded.
+    pub async fn next_packet(&mut self) -> Result<SbusPacket, SbusReadError<R::Error>> {
+        loop {
+            let mut byte = [0u8; 1];
+            let n = self
+                .reader
+                .read(&mut byte)
+                .await
+                .map_err(SbusReadError::Io)?;
+            if n == 0 {
+                return Err(SbusReadError::Eof);
+            }
+            if let Some(result) = self.parser.push_byte(byte[0]) {
+                return result.map_err(SbusReadError::Parser);
+            }
+        }
+    }
+
+    /// Forces the underlying parser back to [`crate::State::AwaitingHead`],
+    /// e.g. after a read timeout.
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
+
+    /// Converts into a [`futures_core::Stream`] of decoded packets.
+    ///
+    /// The in-flight `next_packet` future is boxed and kept alive across
+    /// `poll_next` calls rather than recreated each time, so a transport
+    /// (e.g. a DMA-backed UART) whose `read()` future must run to completion
+    /// is never dropped mid-transfer.
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> SbusPacketStream<R>
+    where
+        R: 'static,
+    {
+        SbusPacketStream {
+            future: alloc::boxed::Box::pin(next_packet_future(self)),
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+type NextPacketOutput<R> = (
+    Result<SbusPacket, SbusReadError<<R as embedded_io_async::ErrorType>::Error>>,
+    AsyncSbusReader<R>,
+);
+
+#[cfg(feature = "futures")]
+async fn next_packet_future<R: embedded_io_async::Read>(
+    mut reader: AsyncSbusReader<R>,
+) -> NextPacketOutput<R> {
+    let result = reader.next_packet().await;
+    (result, reader)
+}
+
+/// [`futures_core::Stream`] of decoded packets built by
+/// [`AsyncSbusReader::into_stream`].
+#[cfg(feature = "futures")]
+pub struct SbusPacketStream<R: embedded_io_async::Read + 'static> {
+    future:
+        core::pin::Pin<alloc::boxed::Box<dyn core::future::Future<Output = NextPacketOutput<R>>>>,
+}
+
+#[cfg(feature = "futures")]
+impl<R: embedded_io_async::Read + 'static> futures_core::Stream for SbusPacketStream<R> {
+    type Item = Result<SbusPacket, SbusReadError<R::Error>>;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        use core::future::Future;
+
+        // `Self` holds only a `Pin<Box<..>>`, which is `Unpin` regardless of
+        // what it points to, so projecting `&mut self.future` out of
+        // `Pin<&mut Self>` is safe without any unsafe code.
+        let this = self.get_mut();
+        match this.future.as_mut().poll(cx) {
+            core::task::Poll::Pending => core::task::Poll::Pending,
+            core::task::Poll::Ready((result, reader)) => {
+                this.future = alloc::boxed::Box::pin(next_packet_future(reader));
+                core::task::Poll::Ready(Some(result))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+
+    const VALID_FRAME: [u8; 25] = [
+        0x0F, 0xE0, 0x03, 0x1F, 0x58, 0xC0, 0x07, 0x16, 0xB0, 0x80, 0x05, 0x2C, 0x60, 0x01, 0x0B,
+        0xF8, 0xC0, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00,
+    ];
+
+    fn expected_packet() -> SbusPacket {
+        SbusPacket {
+            channels: [
+                992, 992, 352, 992, 352, 352, 352, 352, 352, 352, 992, 992, 0, 0, 0, 0,
+            ],
+            channel_17: true,
+            channel_18: true,
+            failsafe: false,
+            frame_lost: false,
+        }
+    }
+
+    #[cfg(feature = "embedded-io")]
+    mod blocking {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct MockError;
+
+        impl embedded_io::Error for MockError {
+            fn kind(&self) -> embedded_io::ErrorKind {
+                embedded_io::ErrorKind::Other
+            }
+        }
+
+        struct MockReader {
+            bytes: std::vec::Vec<u8>,
+            pos: usize,
+        }
+
+        impl embedded_io::ErrorType for MockReader {
+            type Error = MockError;
+        }
+
+        impl embedded_io::Read for MockReader {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                if self.pos >= self.bytes.len() {
+                    return Ok(0);
+                }
+                buf[0] = self.bytes[self.pos];
+                self.pos += 1;
+                Ok(1)
+            }
+        }
+
+        #[test]
+        fn read_packet_decodes_full_frame() {
+            let mut reader = SbusReader::new(MockReader {
+                bytes: VALID_FRAME.to_vec(),
+                pos: 0,
+            });
+            assert_eq!(reader.read_packet(), Ok(expected_packet()));
+        }
+
+        #[test]
+        fn read_packet_reports_eof() {
+            let mut reader = SbusReader::new(MockReader {
+                bytes: std::vec::Vec::new(),
+                pos: 0,
+            });
+            assert!(matches!(reader.read_packet(), Err(SbusReadError::Eof)));
+        }
+
+        #[test]
+        fn reset_discards_partial_frame() {
+            let mut reader = SbusReader::new(MockReader {
+                bytes: VALID_FRAME[..5].to_vec(),
+                pos: 0,
+            });
+            // The partial frame is consumed into the parser before EOF.
+            assert!(matches!(reader.read_packet(), Err(SbusReadError::Eof)));
+            reader.reset();
+            reader.reader.bytes.extend_from_slice(&VALID_FRAME);
+            assert_eq!(reader.read_packet(), Ok(expected_packet()));
+        }
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    mod asynchronous {
+        use super::*;
+        use core::future::Future;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::task::{Context, Poll, Wake};
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct MockError;
+
+        impl embedded_io::Error for MockError {
+            fn kind(&self) -> embedded_io::ErrorKind {
+                embedded_io::ErrorKind::Other
+            }
+        }
+
+        /// An async reader that yields `Pending` once on its very first
+        /// `read()` call before ever producing a byte, to exercise the
+        /// persisted-future path of [`AsyncSbusReader`]/[`SbusPacketStream`].
+        struct FlakyReader {
+            bytes: std::vec::Vec<u8>,
+            pos: usize,
+            pended_once: bool,
+            read_calls: Rc<Cell<u32>>,
+        }
+
+        impl embedded_io_async::ErrorType for FlakyReader {
+            type Error = MockError;
+        }
+
+        impl embedded_io_async::Read for FlakyReader {
+            async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+                self.read_calls.set(self.read_calls.get() + 1);
+                if !self.pended_once {
+                    self.pended_once = true;
+                    PendOnce { polled: false }.await;
+                }
+                if self.pos >= self.bytes.len() {
+                    return Ok(0);
+                }
+                buf[0] = self.bytes[self.pos];
+                self.pos += 1;
+                Ok(1)
+            }
+        }
+
+        struct PendOnce {
+            polled: bool,
+        }
+
+        impl core::future::Future for PendOnce {
+            type Output = ();
+
+            fn poll(
+                mut self: core::pin::Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Self::Output> {
+                if self.polled {
+                    Poll::Ready(())
+                } else {
+                    self.polled = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }
+        }
+
+        struct NoopWake;
+        impl Wake for NoopWake {
+            fn wake(self: std::sync::Arc<Self>) {}
+        }
+
+        fn noop_context() -> Context<'static> {
+            static WAKER: std::sync::OnceLock<std::task::Waker> = std::sync::OnceLock::new();
+            let waker = WAKER.get_or_init(|| std::task::Waker::from(std::sync::Arc::new(NoopWake)));
+            Context::from_waker(waker)
+        }
+
+        #[test]
+        fn next_packet_decodes_full_frame() {
+            let reader = FlakyReader {
+                bytes: VALID_FRAME.to_vec(),
+                pos: 0,
+                pended_once: false,
+                read_calls: Rc::new(Cell::new(0)),
+            };
+            let mut reader = AsyncSbusReader::new(reader);
+            let mut fut = core::pin::pin!(reader.next_packet());
+            let mut cx = noop_context();
+            loop {
+                match fut.as_mut().poll(&mut cx) {
+                    Poll::Ready(result) => {
+                        assert_eq!(result, Ok(expected_packet()));
+                        break;
+                    }
+                    Poll::Pending => continue,
+                }
+            }
+        }
+
+        #[test]
+        fn next_packet_reports_eof() {
+            let reader = FlakyReader {
+                bytes: std::vec::Vec::new(),
+                pos: 0,
+                pended_once: true,
+                read_calls: Rc::new(Cell::new(0)),
+            };
+            let mut reader = AsyncSbusReader::new(reader);
+            let mut fut = core::pin::pin!(reader.next_packet());
+            let mut cx = noop_context();
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => assert!(matches!(result, Err(SbusReadError::Eof))),
+                Poll::Pending => panic!("unexpected Pending"),
+            }
+        }
+
+        #[test]
+        fn stream_persists_pending_future_across_polls() {
+            let read_calls = Rc::new(Cell::new(0));
+            let reader = FlakyReader {
+                bytes: VALID_FRAME.to_vec(),
+                pos: 0,
+                pended_once: false,
+                read_calls: read_calls.clone(),
+            };
+            let reader = AsyncSbusReader::new(reader);
+            let mut stream = reader.into_stream();
+            let mut cx = noop_context();
+
+            loop {
+                match futures_core::Stream::poll_next(core::pin::Pin::new(&mut stream), &mut cx) {
+                    Poll::Ready(Some(result)) => {
+                        assert_eq!(result, Ok(expected_packet()));
+                        break;
+                    }
+                    Poll::Ready(None) => panic!("stream ended early"),
+                    Poll::Pending => continue,
+                }
+            }
+
+            // One `read()` call per byte of the 25-byte frame. If the stream
+            // had recreated (instead of resumed) the in-flight future after
+            // the first `Pending`, the aborted attempt's read of byte 0
+            // would have counted too, and this would be 26.
+            assert_eq!(read_calls.get(), 25);
+        }
+    }
+}
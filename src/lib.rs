@@ -1,5 +1,9 @@
 #![no_std]
 
+pub mod fport;
+pub mod io;
+pub mod telemetry;
+
 const SBUS_PACKET_SIZE: usize = 25;
 const SBUS_NUM_CHANNELS: usize = 16;
 const SBUS_HEADER: u8 = 0x0F;
@@ -100,44 +104,67 @@ pub enum State {
     Reading(usize),
 }
 
-#[derive(Debug, Default)]
+/// Inter-byte gap, in microseconds, above which [`SbusParser::push_byte_timed`]
+/// assumes framing was lost and forces a resync on the next `SBUS_HEADER`.
+/// SBUS bytes within a frame arrive back-to-back (~120us each); frames
+/// themselves repeat every few milliseconds, leaving a multi-millisecond
+/// idle gap in between.
+const DEFAULT_IDLE_THRESHOLD_US: u64 = 2_000;
+
+#[derive(Debug)]
 pub struct SbusParser {
     buffer: [u8; SBUS_PACKET_SIZE],
     state: State,
+    last_byte_us: Option<u64>,
+    idle_threshold_us: u64,
 }
 
-pub struct PacketIterator<'a, 'b> {
-    parser: &'a mut SbusParser,
-    remaining_data: &'b [u8],
+impl Default for SbusParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Iterator for PacketIterator<'_, '_> {
-    type Item = Result<SbusPacket, SbusParserError>;
+/// Uniform encode/decode unit for a single `SBUS_PACKET_SIZE`-byte frame.
+/// Implemented by [`SbusPacket`] (full channel + flag decode) and
+/// [`RawSbusPacket`] (pass-through), this is what lets [`PacketIterator`]
+/// stay a single generic adapter instead of one copy per output type; third
+/// parties can implement it for their own frame layouts (e.g. a
+/// channels-only decoder that skips flag parsing) and still drive it off
+/// [`SbusParser::iter_packets_as`].
+pub trait SbusCodec: Sized {
+    fn from_bytes(raw: &[u8; SBUS_PACKET_SIZE]) -> Result<Self, SbusParserError>;
+    fn to_bytes(&self, buf: &mut [u8; SBUS_PACKET_SIZE]);
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.remaining_data.is_empty() {
-                break;
-            }
+impl SbusCodec for SbusPacket {
+    fn from_bytes(raw: &[u8; SBUS_PACKET_SIZE]) -> Result<Self, SbusParserError> {
+        Ok(SbusPacket::parse(&RawSbusPacket::new(raw)))
+    }
 
-            let byte = self.remaining_data[0];
-            self.remaining_data = &self.remaining_data[1..];
+    fn to_bytes(&self, buf: &mut [u8; SBUS_PACKET_SIZE]) {
+        encode_packet(buf, self);
+    }
+}
 
-            if let Some(result) = self.parser.push_byte(byte) {
-                return Some(result);
-            }
-        }
-        None
+impl SbusCodec for RawSbusPacket {
+    fn from_bytes(raw: &[u8; SBUS_PACKET_SIZE]) -> Result<Self, SbusParserError> {
+        Ok(RawSbusPacket::new(raw))
+    }
+
+    fn to_bytes(&self, buf: &mut [u8; SBUS_PACKET_SIZE]) {
+        *buf = self.bytes;
     }
 }
 
-pub struct RawPacketIterator<'a, 'b> {
+pub struct PacketIterator<'a, 'b, T> {
     parser: &'a mut SbusParser,
     remaining_data: &'b [u8],
+    _codec: core::marker::PhantomData<T>,
 }
 
-impl Iterator for RawPacketIterator<'_, '_> {
-    type Item = Result<RawSbusPacket, SbusParserError>;
+impl<T: SbusCodec> Iterator for PacketIterator<'_, '_, T> {
+    type Item = Result<T, SbusParserError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
@@ -148,7 +175,7 @@ impl Iterator for RawPacketIterator<'_, '_> {
             let byte = self.remaining_data[0];
             self.remaining_data = &self.remaining_data[1..];
 
-            if let Some(result) = self.parser.push_byte_raw(byte) {
+            if let Some(result) = self.parser.push_byte_as::<T>(byte) {
                 return Some(result);
             }
         }
@@ -161,9 +188,21 @@ impl SbusParser {
         Self {
             buffer: [0; SBUS_PACKET_SIZE],
             state: State::AwaitingHead,
+            last_byte_us: None,
+            idle_threshold_us: DEFAULT_IDLE_THRESHOLD_US,
         }
     }
-    pub fn push_byte_raw(&mut self, byte: u8) -> Option<Result<RawSbusPacket, SbusParserError>> {
+
+    /// Overrides the inter-byte gap (in microseconds) that
+    /// [`Self::push_byte_timed`] treats as a lost frame.
+    pub fn set_idle_threshold_us(&mut self, idle_threshold_us: u64) {
+        self.idle_threshold_us = idle_threshold_us;
+    }
+
+    /// Generic primitive behind [`Self::push_byte`] and [`Self::push_byte_raw`]:
+    /// runs the framing state machine and, once a full frame lands, decodes
+    /// it via `T::from_bytes` instead of a type baked into the match arms.
+    pub fn push_byte_as<T: SbusCodec>(&mut self, byte: u8) -> Option<Result<T, SbusParserError>> {
         match self.state {
             State::AwaitingHead => {
                 if byte == SBUS_HEADER {
@@ -183,20 +222,63 @@ impl SbusParser {
         }
         None
     }
+
+    pub fn push_byte_raw(&mut self, byte: u8) -> Option<Result<RawSbusPacket, SbusParserError>> {
+        self.push_byte_as::<RawSbusPacket>(byte)
+    }
+
     pub fn push_byte(&mut self, byte: u8) -> Option<Result<SbusPacket, SbusParserError>> {
+        self.push_byte_as::<SbusPacket>(byte)
+    }
+
+    /// Like [`Self::push_byte_raw`], but `now_us` (a free-running microsecond
+    /// timestamp) is compared against the previous byte's timestamp first:
+    /// if the gap exceeds the configured idle threshold the parser is forced
+    /// back to [`State::AwaitingHead`] before the byte is processed, so a
+    /// stray `SBUS_HEADER` value inside channel data can no longer wedge
+    /// framing until a full 25-byte window passes.
+    pub fn push_byte_raw_timed(
+        &mut self,
+        byte: u8,
+        now_us: u64,
+    ) -> Option<Result<RawSbusPacket, SbusParserError>> {
+        if let Some(last_byte_us) = self.last_byte_us {
+            if now_us.wrapping_sub(last_byte_us) > self.idle_threshold_us {
+                self.state = State::AwaitingHead;
+            }
+        }
+        self.last_byte_us = Some(now_us);
         self.push_byte_raw(byte)
+    }
+
+    /// Timestamped twin of [`Self::push_byte`]. See
+    /// [`Self::push_byte_raw_timed`] for the resync behavior.
+    pub fn push_byte_timed(
+        &mut self,
+        byte: u8,
+        now_us: u64,
+    ) -> Option<Result<SbusPacket, SbusParserError>> {
+        self.push_byte_raw_timed(byte, now_us)
             .map(|res| res.map(|raw_packet| SbusPacket::parse(&raw_packet)))
     }
 
     pub fn reset(&mut self) {
         self.state = State::AwaitingHead;
+        self.last_byte_us = None;
+    }
+
+    /// Footer byte of the most recently completed frame, used by
+    /// [`crate::telemetry`] to tell a plain end-of-frame from a telemetry
+    /// marker without re-deriving it from the raw packet.
+    pub(crate) fn last_footer(&self) -> u8 {
+        self.buffer[SBUS_PACKET_SIZE - 1]
     }
 
-    fn try_parse(&self) -> Result<RawSbusPacket, SbusParserError> {
+    fn try_parse<T: SbusCodec>(&self) -> Result<T, SbusParserError> {
         if self.state != State::Reading(SBUS_PACKET_SIZE) {
             self.validate_frame()?;
         }
-        Ok(RawSbusPacket::new(&self.buffer))
+        T::from_bytes(&self.buffer)
     }
 
     pub fn validate_frame(&self) -> Result<(), SbusParserError> {
@@ -211,16 +293,30 @@ impl SbusParser {
             Ok(())
         }
     }
-    pub fn iter_packets<'a, 'b>(&'a mut self, data: &'b [u8]) -> PacketIterator<'a, 'b> {
-        PacketIterator {
-            parser: self,
-            remaining_data: data,
-        }
+    pub fn iter_packets<'a, 'b>(
+        &'a mut self,
+        data: &'b [u8],
+    ) -> PacketIterator<'a, 'b, SbusPacket> {
+        self.iter_packets_as(data)
+    }
+
+    pub fn iter_packets_raw<'a, 'b>(
+        &'a mut self,
+        data: &'b [u8],
+    ) -> PacketIterator<'a, 'b, RawSbusPacket> {
+        self.iter_packets_as(data)
     }
-    pub fn iter_packets_raw<'a, 'b>(&'a mut self, data: &'b [u8]) -> RawPacketIterator<'a, 'b> {
-        RawPacketIterator {
+
+    /// Generic twin of [`Self::iter_packets`]/[`Self::iter_packets_raw`] for
+    /// any other [`SbusCodec`] implementation.
+    pub fn iter_packets_as<'a, 'b, T: SbusCodec>(
+        &'a mut self,
+        data: &'b [u8],
+    ) -> PacketIterator<'a, 'b, T> {
+        PacketIterator {
             parser: self,
             remaining_data: data,
+            _codec: core::marker::PhantomData,
         }
     }
 }
@@ -397,6 +493,36 @@ mod tests {
         assert!(err == Err(SbusParserError::InvalidFlags(0xff)));
     }
 
+    #[test]
+    fn test_timed_resync_on_idle_gap() {
+        let mut p = SbusParser::new();
+
+        assert!(p.push_byte_timed(SBUS_HEADER, 0).is_none());
+        assert!(p.push_byte_timed(0xAB, 120).is_none());
+        assert!(p.push_byte_timed(0xCD, 240).is_none());
+        assert!(p.state == State::Reading(3));
+
+        // A multi-millisecond idle gap follows (e.g. the transmitter stalled
+        // mid-frame); the next byte is the header of a fresh frame and must
+        // force a resync instead of being swallowed as payload byte 4.
+        let resync_at = 240 + DEFAULT_IDLE_THRESHOLD_US + 1;
+        assert!(p.push_byte_timed(RAW_BYTES[0], resync_at).is_none());
+        assert!(p.state == State::Reading(1));
+
+        for (i, b) in RAW_BYTES[1..RAW_BYTES.len() - 1].iter().enumerate() {
+            let now = resync_at + 120 * (i as u64 + 1);
+            assert!(p.push_byte_timed(*b, now).is_none());
+        }
+        let packet = p
+            .push_byte_timed(RAW_BYTES[24], resync_at + 120 * 24)
+            .unwrap()
+            .unwrap();
+        assert!(
+            packet.channels
+                == [992, 992, 352, 992, 352, 352, 352, 352, 352, 352, 992, 992, 0, 0, 0, 0,]
+        );
+    }
+
     #[test]
     fn test_basic_raw_packet() {
         let mut p = SbusParser::new();
@@ -457,4 +583,34 @@ mod tests {
         assert!(SbusPacket::parse(&raw_results[1].as_ref().unwrap()) == expected);
         assert!(raw_results[4].is_err());
     }
+
+    /// A minimal third-party codec that only decodes channel 0, to prove
+    /// `iter_packets_as` works for types other than `SbusPacket`/`RawSbusPacket`.
+    struct FirstChannelOnly(u16);
+
+    impl SbusCodec for FirstChannelOnly {
+        fn from_bytes(raw: &[u8; SBUS_PACKET_SIZE]) -> Result<Self, SbusParserError> {
+            SbusPacket::from_bytes(raw).map(|packet| FirstChannelOnly(packet.channels[0]))
+        }
+
+        fn to_bytes(&self, buf: &mut [u8; SBUS_PACKET_SIZE]) {
+            let packet = SbusPacket {
+                channels: [self.0; SBUS_NUM_CHANNELS],
+                channel_17: false,
+                channel_18: false,
+                failsafe: false,
+                frame_lost: false,
+            };
+            encode_packet(buf, &packet);
+        }
+    }
+
+    #[test]
+    fn test_custom_codec_via_iter_packets_as() {
+        let mut parser = SbusParser::new();
+        let results: std::vec::Vec<Result<FirstChannelOnly, SbusParserError>> =
+            parser.iter_packets_as(&RAW_BYTES).collect();
+        assert!(results.len() == 1);
+        assert!(results[0].as_ref().unwrap().0 == 992);
+    }
 }
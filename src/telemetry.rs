@@ -0,0 +1,209 @@
+//! SBUS2 telemetry slot decoding.
+//!
+//! A telemetry footer (`0x04`/`0x14`/`0x24`/`0x34`) is followed by a single
+//! 3-byte slot frame (slot id + 2 data bytes) before the next control frame's
+//! header; the footer selects which slot group (0-7, 8-15, 16-23, 24-31) that
+//! slot belongs to. [`SbusTelemetry`] decodes both off the same byte stream.
+
+use crate::{SbusPacket, SbusParser, SbusParserError};
+
+/// Number of slots in each telemetry group identified by a footer byte.
+pub const TELEMETRY_SLOTS_PER_GROUP: u8 = 8;
+const TELEMETRY_SLOT_SIZE: usize = 3;
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TelemetrySlot {
+    pub id: u8,
+    pub data: u16,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SbusFrame {
+    Control(SbusPacket),
+    Telemetry(TelemetrySlot),
+}
+
+fn slot_group_base(footer: u8) -> Option<u8> {
+    match footer {
+        0x04 => Some(0),
+        0x14 => Some(8),
+        0x24 => Some(16),
+        0x34 => Some(24),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+enum TelemetryState {
+    #[default]
+    Control,
+    Slot {
+        base: u8,
+        buffer: [u8; TELEMETRY_SLOT_SIZE],
+        read: usize,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct SbusTelemetry {
+    parser: SbusParser,
+    state: TelemetryState,
+}
+
+pub struct TelemetryIterator<'a, 'b> {
+    telemetry: &'a mut SbusTelemetry,
+    remaining_data: &'b [u8],
+}
+
+impl Iterator for TelemetryIterator<'_, '_> {
+    type Item = Result<SbusFrame, SbusParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining_data.is_empty() {
+                break;
+            }
+
+            let byte = self.remaining_data[0];
+            self.remaining_data = &self.remaining_data[1..];
+
+            if let Some(result) = self.telemetry.push_byte_telemetry(byte) {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+impl SbusTelemetry {
+    pub fn new() -> Self {
+        Self {
+            parser: SbusParser::new(),
+            state: TelemetryState::Control,
+        }
+    }
+
+    /// Feeds one byte through the control-frame parser, then, once a frame
+    /// with a telemetry footer completes, through the trailing slot frame it
+    /// identifies, yielding whichever kind finishes first.
+    pub fn push_byte_telemetry(&mut self, byte: u8) -> Option<Result<SbusFrame, SbusParserError>> {
+        match self.state {
+            TelemetryState::Control => {
+                let result = self.parser.push_byte(byte)?;
+                match result {
+                    Ok(packet) => {
+                        if let Some(base) = slot_group_base(self.parser.last_footer()) {
+                            self.state = TelemetryState::Slot {
+                                base,
+                                buffer: [0; TELEMETRY_SLOT_SIZE],
+                                read: 0,
+                            };
+                        }
+                        Some(Ok(SbusFrame::Control(packet)))
+                    }
+                    Err(e) => Some(Err(e)),
+                }
+            }
+            TelemetryState::Slot {
+                base,
+                mut buffer,
+                read,
+            } => {
+                buffer[read] = byte;
+                let read = read + 1;
+                if read < TELEMETRY_SLOT_SIZE {
+                    self.state = TelemetryState::Slot { base, buffer, read };
+                    return None;
+                }
+                self.state = TelemetryState::Control;
+                // Slot data is little-endian on the wire (low byte first),
+                // matching common SBUS2-capable receiver firmware.
+                let slot = TelemetrySlot {
+                    id: base + (buffer[0] % TELEMETRY_SLOTS_PER_GROUP),
+                    data: u16::from(buffer[1]) | (u16::from(buffer[2]) << 8),
+                };
+                Some(Ok(SbusFrame::Telemetry(slot)))
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.parser.reset();
+        self.state = TelemetryState::Control;
+    }
+
+    pub fn iter_frames<'a, 'b>(&'a mut self, data: &'b [u8]) -> TelemetryIterator<'a, 'b> {
+        TelemetryIterator {
+            telemetry: self,
+            remaining_data: data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+
+    const CONTROL_WITH_TELEMETRY: [u8; 25] = [
+        0x0F, 0xE0, 0x03, 0x1F, 0x58, 0xC0, 0x07, 0x16, 0xB0, 0x80, 0x05, 0x2C, 0x60, 0x01, 0x0B,
+        0xF8, 0xC0, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x04,
+    ];
+    const SLOT: [u8; 3] = [0x02, 0xAA, 0x55];
+    const CONTROL: [u8; 25] = [
+        0x0F, 0xE0, 0x03, 0x1F, 0x58, 0xC0, 0x07, 0x16, 0xB0, 0x80, 0x05, 0x2C, 0x60, 0x01, 0x0B,
+        0xF8, 0xC0, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00,
+    ];
+
+    fn expected_packet() -> SbusPacket {
+        SbusPacket {
+            channels: [
+                992, 992, 352, 992, 352, 352, 352, 352, 352, 352, 992, 992, 0, 0, 0, 0,
+            ],
+            channel_17: true,
+            channel_18: true,
+            failsafe: false,
+            frame_lost: false,
+        }
+    }
+
+    #[test]
+    fn test_telemetry_slot_between_control_frames() {
+        let mut data = std::vec::Vec::new();
+        data.extend_from_slice(&CONTROL_WITH_TELEMETRY);
+        data.extend_from_slice(&SLOT);
+        data.extend_from_slice(&CONTROL);
+
+        let mut telemetry = SbusTelemetry::new();
+        let frames: std::vec::Vec<Result<SbusFrame, SbusParserError>> =
+            telemetry.iter_frames(&data).collect();
+
+        assert!(frames.len() == 3);
+        assert!(frames[0] == Ok(SbusFrame::Control(expected_packet())));
+        assert!(
+            frames[1]
+                == Ok(SbusFrame::Telemetry(TelemetrySlot {
+                    id: 2,
+                    data: 0x55AA,
+                }))
+        );
+        assert!(frames[2] == Ok(SbusFrame::Control(expected_packet())));
+    }
+
+    #[test]
+    fn test_plain_footer_has_no_telemetry_tail() {
+        let mut data = std::vec::Vec::new();
+        data.extend_from_slice(&CONTROL);
+        data.extend_from_slice(&CONTROL);
+
+        let mut telemetry = SbusTelemetry::new();
+        let frames: std::vec::Vec<Result<SbusFrame, SbusParserError>> =
+            telemetry.iter_frames(&data).collect();
+
+        assert!(frames.len() == 2);
+        assert!(frames[0] == Ok(SbusFrame::Control(expected_packet())));
+        assert!(frames[1] == Ok(SbusFrame::Control(expected_packet())));
+    }
+}